@@ -0,0 +1,302 @@
+use crate::protocol::Protocol;
+use crate::resource::{Method, Resource};
+use crate::uri::Uri;
+use crate::{ParseError, RamlSpec};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "unsafe", "use", "where", "while",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    #[error("Failed to resolve base_uri: {0}")]
+    BaseUri(#[from] ParseError),
+    #[error("Failed to write generated client: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl RamlSpec {
+    /// Generates a compilable Rust client: one async function per resource
+    /// method, named from the resource path and verb (e.g.
+    /// `get_orders_by_order_id`), taking each `{param}` captured from the
+    /// URI template as an argument.
+    pub fn generate_client(&self) -> Result<String, CodegenError> {
+        generate(self).map_err(CodegenError::from)
+    }
+
+    /// Like `generate_client`, but writes the generated source to `out`
+    /// instead of returning it.
+    pub fn write_client(&self, out: &mut impl Write) -> Result<(), CodegenError> {
+        out.write_all(self.generate_client()?.as_bytes())?;
+        Ok(())
+    }
+}
+
+struct Endpoint<'a> {
+    path: String,
+    params: Vec<String>,
+    verb: &'a str,
+    method: &'a Method,
+}
+
+fn generate(spec: &RamlSpec) -> Result<String, ParseError> {
+    let mut endpoints = Vec::new();
+    collect_endpoints(&spec.resources, "", &[], &mut endpoints);
+
+    let scheme = preferred_scheme(spec.protocols.as_ref());
+    let base_uri_expanded = spec.base_uri_expanded()?;
+    let base_uri = base_uri_expanded
+        .as_ref()
+        .map(Uri::as_str)
+        .map(strip_scheme)
+        .unwrap_or_default();
+
+    let mut used_names = HashMap::new();
+    let mut code = String::new();
+    code.push_str("// Generated by raml_spec::codegen. Do not edit by hand.\n\n");
+
+    for endpoint in &endpoints {
+        let name = unique_name(function_name(endpoint.verb, &endpoint.path), &mut used_names);
+        code.push_str(&render_endpoint(endpoint, &name, scheme, base_uri));
+        code.push('\n');
+    }
+
+    Ok(code)
+}
+
+fn collect_endpoints<'a>(
+    resources: &'a [Resource],
+    path_prefix: &str,
+    params_prefix: &[String],
+    out: &mut Vec<Endpoint<'a>>,
+) {
+    for resource in resources {
+        let path = format!("{path_prefix}{}", resource.relative_uri);
+        let mut params = params_prefix.to_vec();
+        params.extend(resource.path_parameters());
+
+        for (verb, method) in &resource.methods {
+            out.push(Endpoint {
+                path: path.clone(),
+                params: params.clone(),
+                verb,
+                method,
+            });
+        }
+
+        collect_endpoints(&resource.sub_resources, &path, &params, out);
+    }
+}
+
+fn render_endpoint(endpoint: &Endpoint, name: &str, scheme: &str, base_uri: &str) -> String {
+    let mut code = String::new();
+
+    if let Some(description) = endpoint.method.description.as_deref() {
+        let _ = writeln!(code, "/// {description}");
+    }
+
+    let idents = endpoint.params.iter().map(|param| to_rust_ident(param)).collect::<Vec<_>>();
+    let args = idents
+        .iter()
+        .map(|ident| format!("{ident}: &str"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = writeln!(
+        code,
+        "pub async fn {name}({args}) -> Result<reqwest::Response, reqwest::Error> {{"
+    );
+
+    let _ = writeln!(code, "    let url = format!(");
+    let _ = writeln!(
+        code,
+        "        \"{scheme}://{base_uri}{}\",",
+        positional_format_string(&endpoint.path)
+    );
+    for ident in &idents {
+        let _ = writeln!(code, "        {ident},");
+    }
+    let _ = writeln!(code, "    );");
+
+    let _ = writeln!(
+        code,
+        "    reqwest::Client::new()\n        .request(reqwest::Method::{}, &url)\n        .send()\n        .await",
+        endpoint.verb.to_uppercase()
+    );
+    let _ = writeln!(code, "}}");
+
+    code
+}
+
+/// Replaces every `{name}` template in `path` with a positional `{}`
+/// placeholder. `{name}` isn't always a valid format-string key (RAML path
+/// parameters may contain hyphens or start with a digit), so the generated
+/// `format!` call binds values positionally instead, in the same left-to-
+/// right order the placeholders appear in `path`.
+fn positional_format_string(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        match rest[start..].find('}') {
+            Some(offset) => {
+                result.push_str("{}");
+                rest = &rest[start + offset + 1..];
+            }
+            None => break,
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// `base_uri` already carries its own scheme (e.g. `https://example.com/api`),
+/// which would otherwise double up with the `{scheme}://` we prepend based on
+/// `protocols`. Strip it so the generated URL has exactly one.
+fn strip_scheme(uri: &str) -> &str {
+    uri.split_once("://").map_or(uri, |(_, rest)| rest)
+}
+
+fn preferred_scheme(protocols: Option<&HashSet<Protocol>>) -> &'static str {
+    match protocols {
+        Some(protocols) if protocols.contains(&Protocol::Https) => "https",
+        Some(protocols) if protocols.contains(&Protocol::Http) => "http",
+        _ => "https",
+    }
+}
+
+fn function_name(verb: &str, path: &str) -> String {
+    let mut parts = vec![verb.to_string()];
+    parts.extend(path.split('/').filter(|segment| !segment.is_empty()).map(segment_to_ident));
+    escape_keyword(&parts.join("_"))
+}
+
+fn segment_to_ident(segment: &str) -> String {
+    match segment.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')) {
+        Some(param) => format!("by_{}", to_snake_case(param)),
+        None => to_snake_case(segment),
+    }
+}
+
+fn to_snake_case(value: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in value.chars().enumerate() {
+        if ch.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else if ch.is_alphanumeric() {
+            out.push(ch);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+/// Snake-cases `param` into a valid Rust identifier, escaping keywords and
+/// prefixing an underscore if it would otherwise start with a digit (e.g. a
+/// RAML path parameter `{2fa}`, which is legal URI-template syntax but not a
+/// legal Rust identifier on its own).
+fn to_rust_ident(param: &str) -> String {
+    let snake = to_snake_case(param);
+    let snake = match snake.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("_{snake}"),
+        _ => snake,
+    };
+    escape_keyword(&snake)
+}
+
+fn escape_keyword(ident: &str) -> String {
+    if RUST_KEYWORDS.contains(&ident) {
+        format!("r#{ident}")
+    } else {
+        ident.to_string()
+    }
+}
+
+fn unique_name(name: String, used: &mut HashMap<String, usize>) -> String {
+    let count = used.entry(name.clone()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        name
+    } else {
+        format!("{name}_{}", *count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn function_name_snake_cases_hyphenated_and_numeric_params() {
+        assert_eq!(function_name("get", "/orders/{order-id}"), "get_orders_by_order_id");
+        assert_eq!(function_name("get", "/{2fa}"), "get_by_2fa");
+    }
+
+    #[test]
+    fn escape_keyword_only_touches_reserved_words() {
+        assert_eq!(escape_keyword("type"), "r#type");
+        assert_eq!(escape_keyword("order_id"), "order_id");
+    }
+
+    #[test]
+    fn to_rust_ident_handles_hyphens_digits_and_keywords() {
+        assert_eq!(to_rust_ident("order-id"), "order_id");
+        assert_eq!(to_rust_ident("2fa"), "_2fa");
+        assert_eq!(to_rust_ident("type"), "r#type");
+    }
+
+    #[test]
+    fn unique_name_dedups_with_numeric_suffixes() {
+        let mut used = HashMap::new();
+        assert_eq!(unique_name("foo".to_string(), &mut used), "foo");
+        assert_eq!(unique_name("foo".to_string(), &mut used), "foo_2");
+        assert_eq!(unique_name("foo".to_string(), &mut used), "foo_3");
+    }
+
+    #[test]
+    fn positional_format_string_replaces_templates_in_order() {
+        assert_eq!(positional_format_string("/orders/{order-id}"), "/orders/{}");
+        assert_eq!(positional_format_string("/orders"), "/orders");
+        assert_eq!(
+            positional_format_string("/{a}/orders/{b}"),
+            "/{}/orders/{}"
+        );
+    }
+
+    #[test]
+    fn generated_client_uses_positional_args_for_non_identifier_params() {
+        let mut reader = Cursor::new(
+            "title: T\nbaseUri: https://example.com/api\nprotocols:\n  - HTTPS\n/orders/{order-id}:\n  get: {}\n/{2fa}:\n  get: {}\n",
+        );
+        let spec = RamlSpec::from_reader(&mut reader).unwrap();
+        let code = spec.generate_client().unwrap();
+
+        assert!(!code.contains("{order-id}"));
+        assert!(!code.contains("order-id ="));
+        assert!(code.contains("fn get_orders_by_order_id(order_id: &str"));
+        assert!(code.contains("fn get_by_2fa(_2fa: &str"));
+    }
+
+    #[test]
+    fn generated_client_expands_templated_base_uri() {
+        let mut reader = Cursor::new(
+            "title: T\nversion: v1\nbaseUri: \"https://{host}/api/{version}\"\nbaseUriParameters:\n  host: example.com\nprotocols:\n  - HTTPS\n/orders:\n  get: {}\n",
+        );
+        let spec = RamlSpec::from_reader(&mut reader).unwrap();
+        let code = spec.generate_client().unwrap();
+
+        assert!(code.contains("https://example.com/api/v1/orders"));
+        assert!(!code.contains("{host}"));
+        assert!(!code.contains("{version}"));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::ParseError;
+use serde_yaml::{value::TaggedValue, Value};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+const INCLUDE_TAG: &str = "!include";
+
+/// Recursively resolves `!include <target>` tags found in `value`, splicing
+/// in the referenced document in place of the tag. `base_dir` is the
+/// directory `!include` targets are resolved relative to; it is `None` when
+/// parsing from a reader with no file of origin, in which case any
+/// `!include` tag is an error. `visited` guards against include cycles: it
+/// holds the documents currently being loaded, and starts out with the root
+/// document's own path when it has one (empty otherwise).
+pub(crate) fn resolve(
+    value: Value,
+    base_dir: Option<&Path>,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<Value, ParseError> {
+    match value {
+        Value::Tagged(tagged) if tagged.tag == INCLUDE_TAG => {
+            let base_dir = base_dir.ok_or(ParseError::IncludeNotSupported)?;
+            let target = tagged
+                .value
+                .as_str()
+                .ok_or(ParseError::IncludeNotSupported)?;
+            load_include(base_dir, target, visited)
+        }
+        Value::Tagged(tagged) => Ok(Value::Tagged(Box::new(TaggedValue {
+            tag: tagged.tag,
+            value: resolve(tagged.value, base_dir, visited)?,
+        }))),
+        Value::Sequence(sequence) => Ok(Value::Sequence(
+            sequence
+                .into_iter()
+                .map(|item| resolve(item, base_dir, visited))
+                .collect::<Result<_, _>>()?,
+        )),
+        Value::Mapping(mapping) => Ok(Value::Mapping(
+            mapping
+                .into_iter()
+                .map(|(key, value)| -> Result<_, ParseError> {
+                    Ok((key, resolve(value, base_dir, visited)?))
+                })
+                .collect::<Result<serde_yaml::Mapping, ParseError>>()?,
+        )),
+        other => Ok(other),
+    }
+}
+
+fn load_include(
+    base_dir: &Path,
+    target: &str,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<Value, ParseError> {
+    let path = base_dir.join(target);
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+    if !visited.insert(canonical.clone()) {
+        return Err(ParseError::IncludeCycle(canonical));
+    }
+
+    let contents = std::fs::read_to_string(&path).map_err(|source| ParseError::IncludeIo {
+        path: path.clone(),
+        source,
+    })?;
+
+    let included = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("md") | Some("json") => Value::String(contents),
+        _ => serde_yaml::from_str(&contents)
+            .map_err(|error| ParseError::from(error).at(&path))?,
+    };
+
+    let include_dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| base_dir.to_path_buf());
+    let resolved = resolve(included, Some(&include_dir), visited)?;
+    visited.remove(&canonical);
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "raml_spec_include_test_{name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn splices_in_a_yaml_include() {
+        let dir = temp_dir("yaml");
+        fs::write(dir.join("target.raml"), "foo: bar\n").unwrap();
+        let value: Value = serde_yaml::from_str("root: !include target.raml\n").unwrap();
+
+        let resolved = resolve(value, Some(&dir), &mut HashSet::new()).unwrap();
+
+        assert_eq!(
+            resolved
+                .get("root")
+                .and_then(|root| root.get("foo"))
+                .and_then(Value::as_str),
+            Some("bar")
+        );
+    }
+
+    #[test]
+    fn splices_json_and_md_targets_as_raw_strings() {
+        let dir = temp_dir("raw");
+        fs::write(dir.join("schema.json"), "{\"type\": \"object\"}").unwrap();
+        fs::write(dir.join("notes.md"), "# Notes\n").unwrap();
+        let value: Value = serde_yaml::from_str(
+            "schema: !include schema.json\nnotes: !include notes.md\n",
+        )
+        .unwrap();
+
+        let resolved = resolve(value, Some(&dir), &mut HashSet::new()).unwrap();
+
+        assert_eq!(
+            resolved.get("schema").and_then(Value::as_str),
+            Some("{\"type\": \"object\"}")
+        );
+        assert_eq!(
+            resolved.get("notes").and_then(Value::as_str),
+            Some("# Notes\n")
+        );
+    }
+
+    #[test]
+    fn errors_without_a_base_dir() {
+        let value: Value = serde_yaml::from_str("root: !include target.raml\n").unwrap();
+
+        let error = resolve(value, None, &mut HashSet::new()).unwrap_err();
+
+        assert!(matches!(error, ParseError::IncludeNotSupported));
+    }
+
+    #[test]
+    fn detects_include_cycles() {
+        let dir = temp_dir("cycle");
+        fs::write(dir.join("a.raml"), "b: !include b.raml\n").unwrap();
+        fs::write(dir.join("b.raml"), "a: !include a.raml\n").unwrap();
+        let value: Value = serde_yaml::from_str("root: !include a.raml\n").unwrap();
+
+        let error = resolve(value, Some(&dir), &mut HashSet::new()).unwrap_err();
+
+        assert!(matches!(error, ParseError::IncludeCycle(_)));
+    }
+}
@@ -1,17 +1,20 @@
-use protocol::{Protocol, ProtocolParseError};
-use std::{collections::HashSet, convert::TryFrom, io::Read, str::FromStr};
+use protocol::Protocol;
+use resource::Resource;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, io,
+    io::Read,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 use uri::{ParseError as UriParseError, Uri};
-use yaml_rust::{ScanError, YamlLoader};
 
+pub mod codegen;
+mod include;
+pub mod resource;
 pub mod uri;
 
-const TITLE: &str = "title";
-const DESCRIPTION: &str = "description";
-const VERSION: &str = "version";
-const BASE_URI: &str = "baseUri";
-const BASE_URI_PARAMETERS: &str = "baseUriParameters";
-const PROTOCOLS: &str = "protocols";
 const MEDIA_TYPE: &str = "mediaType";
 const DOCUMENTATION: &str = "documentation";
 const SCHEMAS: &str = "schemas";
@@ -29,58 +32,162 @@ pub struct RamlSpec {
     pub description: Option<String>,
     pub version: Option<String>,
     pub base_uri: Option<Uri>,
+    pub base_uri_parameters: Option<HashMap<String, String>>,
     pub protocols: Option<HashSet<Protocol>>,
+    pub resources: Vec<Resource>,
+}
+
+/// Mirrors `RamlSpec`'s top-level metadata so it can be derived directly; the
+/// resource tree is parsed separately since it lives under arbitrary `/`-prefixed keys.
+#[derive(Debug, Deserialize)]
+struct RamlSpecMeta {
+    title: String,
+    description: Option<String>,
+    version: Option<String>,
+    #[serde(rename = "baseUri")]
+    base_uri: Option<Uri>,
+    #[serde(rename = "baseUriParameters")]
+    base_uri_parameters: Option<HashMap<String, String>>,
+    protocols: Option<HashSet<Protocol>>,
 }
 
 impl RamlSpec {
+    /// Parses a self-contained RAML document with no file of origin. An
+    /// `!include` tag is an error here since there is no base directory to
+    /// resolve it against; use `from_path` for documents that use includes.
     pub fn from_reader(reader: &mut impl Read) -> Result<Self, ParseError> {
-        let mut raml = String::new();
-        let _ = reader
-            .read_to_string(&mut raml)
-            .map_err(|err| err.to_string());
-        let yaml_vec = YamlLoader::load_from_str(&raml)?;
-        let yaml = yaml_vec.first().ok_or(ParseError::FileIsEmpty)?;
-        Ok(Self {
-            title: yaml[TITLE]
-                .as_str()
-                .ok_or(ParseError::FieldNotFound(TITLE.to_string()))?
-                .to_string(),
-            description: yaml[DESCRIPTION].as_str().map(String::from),
-            version: yaml[VERSION].as_str().map(String::from),
-            base_uri: yaml[BASE_URI].as_str().map(FromStr::from_str).transpose()?,
-            protocols: yaml[PROTOCOLS]
-                .as_vec()
-                .map(|protocols| {
-                    protocols
-                        .iter()
-                        .cloned()
-                        .map(Protocol::try_from)
-                        .collect::<Result<HashSet<_>, _>>()
-                })
-                .transpose()?,
+        let value: serde_yaml::Value = serde_yaml::from_reader(reader)?;
+        let value = include::resolve(value, None, &mut HashSet::new())?;
+        Self::from_value(value)
+    }
+
+    /// Parses a RAML document from `path`, resolving any `!include <target>`
+    /// tags relative to the including file's directory. On failure, the
+    /// error is enriched with `path` and, where available, the line/column
+    /// the underlying YAML parser was at.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ParseError> {
+        let path = path.as_ref();
+        Self::from_path_inner(path).map_err(|error| error.at(path))
+    }
+
+    fn from_path_inner(path: &Path) -> Result<Self, ParseError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ParseError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let value: serde_yaml::Value = serde_yaml::from_str(&contents)?;
+
+        let base_dir = path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let mut visited = HashSet::new();
+        visited.insert(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+        let value = include::resolve(value, Some(&base_dir), &mut visited)?;
+
+        Self::from_value(value)
+    }
+
+    fn from_value(value: serde_yaml::Value) -> Result<Self, ParseError> {
+        let mapping = value.as_mapping().cloned().unwrap_or_default();
+        let meta: RamlSpecMeta = serde_yaml::from_value(value)?;
+        let resources = resource::parse_resources(&mapping)?;
+        Ok(RamlSpec {
+            title: meta.title,
+            description: meta.description,
+            version: meta.version,
+            base_uri: meta.base_uri,
+            base_uri_parameters: meta.base_uri_parameters,
+            protocols: meta.protocols,
+            resources,
         })
     }
+
+    /// Returns `base_uri` with any `{name}` templates resolved using
+    /// `base_uri_parameters` and, for `{version}`, `version`.
+    pub fn base_uri_expanded(&self) -> Result<Option<Uri>, ParseError> {
+        self.base_uri
+            .as_ref()
+            .map(|base_uri| {
+                let mut params = self.base_uri_parameters.clone().unwrap_or_default();
+                if let Some(version) = &self.version {
+                    params.insert("version".to_string(), version.clone());
+                }
+                base_uri.expand(&params).map_err(ParseError::from)
+            })
+            .transpose()
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum ParseError {
-    #[error("Field not found: {0}.")]
-    FieldNotFound(String),
     #[error("Incorrect yaml syntax: {0}.")]
-    IncorrectYamlSyntax(#[from] ScanError),
-    #[error("File is empty.")]
-    FileIsEmpty,
+    IncorrectYamlSyntax(#[from] serde_yaml::Error),
     #[error("Incorrect URI: {0}")]
     IncorrectUri(#[from] UriParseError),
-    #[error("Failed to parse rotocol: {0}")]
-    IncorrectProtocol(#[from] ProtocolParseError),
+    #[error("Invalid URI template in \"{uri}\": {reason}")]
+    InvalidUriTemplate { uri: String, reason: String },
+    #[error("Failed to read {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+    #[error("!include needs a string target and a base directory; a reader has no base directory, use RamlSpec::from_path")]
+    IncludeNotSupported,
+    #[error("Include cycle detected at {0}")]
+    IncludeCycle(PathBuf),
+    #[error("Failed to resolve include {path}: {source}")]
+    IncludeIo { path: PathBuf, source: io::Error },
+    #[error("Failed parsing {location}: {source}")]
+    AtLocation {
+        location: SourceLocation,
+        #[source]
+        source: Box<ParseError>,
+    },
 }
 
-pub mod protocol {
-    use std::convert::TryFrom;
+impl ParseError {
+    /// Wraps `self` with the file it was parsed from, attaching the YAML
+    /// parser's line/column when the underlying error exposes one.
+    fn at(self, path: &Path) -> ParseError {
+        let (line, col) = match &self {
+            ParseError::IncorrectYamlSyntax(source) => source
+                .location()
+                .map(|location| (Some(location.line()), Some(location.column())))
+                .unwrap_or((None, None)),
+            _ => (None, None),
+        };
+        ParseError::AtLocation {
+            location: SourceLocation {
+                path: path.to_path_buf(),
+                line,
+                col,
+            },
+            source: Box::new(self),
+        }
+    }
+}
 
+/// A position within a parsed RAML document, used to make `ParseError`
+/// actionable when a file is one of many in a project.
+#[derive(Debug)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub col: Option<usize>,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.col) {
+            (Some(line), Some(col)) => write!(f, "{}:{}:{}", self.path.display(), line, col),
+            _ => write!(f, "{}", self.path.display()),
+        }
+    }
+}
+
+pub mod protocol {
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::Deserialize;
+    use std::fmt;
     use thiserror::Error;
-    use yaml_rust::Yaml;
 
     pub const HTTP: &str = "HTTP";
     pub const HTTPS: &str = "HTTPS";
@@ -95,20 +202,37 @@ pub mod protocol {
     pub enum ProtocolParseError {
         #[error("Unsupported protocol: {0}")]
         UnsupportedProtocol(String),
-        #[error("Expected YAML string.")]
-        InvalidYamlValue,
     }
 
-    impl TryFrom<Yaml> for Protocol {
-        type Error = ProtocolParseError;
+    impl<'de> Deserialize<'de> for Protocol {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct ProtocolVisitor;
 
-        fn try_from(value: Yaml) -> Result<Self, Self::Error> {
-            match value {
-                Yaml::String(protocol) if protocol.as_str() == HTTP => Ok(Protocol::Http),
-                Yaml::String(protocol) if protocol.as_str() == HTTPS => Ok(Protocol::Https),
-                Yaml::String(protocol) => Err(ProtocolParseError::UnsupportedProtocol(protocol)),
-                _ => Err(ProtocolParseError::InvalidYamlValue),
+            impl<'de> Visitor<'de> for ProtocolVisitor {
+                type Value = Protocol;
+
+                fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                    formatter.write_str("\"HTTP\" or \"HTTPS\"")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Protocol, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        HTTP => Ok(Protocol::Http),
+                        HTTPS => Ok(Protocol::Https),
+                        other => Err(de::Error::custom(ProtocolParseError::UnsupportedProtocol(
+                            other.to_string(),
+                        ))),
+                    }
+                }
             }
+
+            deserializer.deserialize_str(ProtocolVisitor)
         }
     }
 }
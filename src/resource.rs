@@ -0,0 +1,174 @@
+use crate::ParseError;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+
+pub const METHODS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+
+const DISPLAY_NAME: &str = "displayName";
+const DESCRIPTION: &str = "description";
+
+#[derive(Debug, Deserialize)]
+pub struct Method {
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct Resource {
+    pub relative_uri: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub methods: HashMap<String, Method>,
+    pub sub_resources: Vec<Resource>,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Parameter(String),
+}
+
+impl Resource {
+    /// Names of the `{identifier}` segments captured by this resource's URI template.
+    pub fn path_parameters(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Parameter(name) => Some(name.clone()),
+                Segment::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    fn from_yaml(relative_uri: String, value: &Value) -> Result<Self, ParseError> {
+        let segments = parse_segments(&relative_uri)?;
+        let mapping = value.as_mapping();
+
+        let display_name = mapping
+            .and_then(|mapping| mapping.get(Value::from(DISPLAY_NAME)))
+            .and_then(Value::as_str)
+            .map(String::from);
+        let description = mapping
+            .and_then(|mapping| mapping.get(Value::from(DESCRIPTION)))
+            .and_then(Value::as_str)
+            .map(String::from);
+
+        let methods = mapping
+            .map(|mapping| {
+                METHODS
+                    .iter()
+                    .filter_map(|&verb| {
+                        mapping
+                            .get(Value::from(verb))
+                            .map(|method| (verb.to_string(), method.clone()))
+                    })
+                    .map(|(verb, method)| {
+                        serde_yaml::from_value::<Method>(method)
+                            .map(|method| (verb, method))
+                            .map_err(ParseError::from)
+                    })
+                    .collect::<Result<HashMap<_, _>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let sub_resources = mapping.map(parse_resources).transpose()?.unwrap_or_default();
+
+        Ok(Resource {
+            relative_uri,
+            display_name,
+            description,
+            methods,
+            sub_resources,
+            segments,
+        })
+    }
+}
+
+/// Builds a `Resource` for every mapping key beginning with `/`, in document order.
+pub(crate) fn parse_resources(mapping: &Mapping) -> Result<Vec<Resource>, ParseError> {
+    mapping
+        .iter()
+        .filter_map(|(key, value)| {
+            key.as_str()
+                .filter(|key| key.starts_with('/'))
+                .map(|key| Resource::from_yaml(key.to_string(), value))
+        })
+        .collect()
+}
+
+fn parse_segments(relative_uri: &str) -> Result<Vec<Segment>, ParseError> {
+    relative_uri
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| parse_segment(relative_uri, segment))
+        .collect()
+}
+
+fn parse_segment(relative_uri: &str, segment: &str) -> Result<Segment, ParseError> {
+    let invalid = |reason: &str| ParseError::InvalidUriTemplate {
+        uri: relative_uri.to_string(),
+        reason: reason.to_string(),
+    };
+
+    if segment.matches('{').count() != segment.matches('}').count() {
+        return Err(invalid("unbalanced braces"));
+    }
+
+    if segment.starts_with('{') && segment.ends_with('}') {
+        let identifier = &segment[1..segment.len() - 1];
+        if identifier.is_empty() || identifier.contains('{') || identifier.contains('}') {
+            return Err(invalid("empty or malformed parameter name"));
+        }
+        Ok(Segment::Parameter(identifier.to_string()))
+    } else if segment.contains('{') || segment.contains('}') {
+        Err(invalid("braces must wrap the entire segment"))
+    } else {
+        Ok(Segment::Literal(segment.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Result<Vec<Resource>, ParseError> {
+        let value: Value = serde_yaml::from_str(yaml).unwrap();
+        parse_resources(value.as_mapping().unwrap())
+    }
+
+    #[test]
+    fn parses_nested_resources_and_path_parameters() {
+        let resources = parse(
+            "/orders:\n  displayName: Orders\n  get:\n    description: List orders\n  /{orderId}:\n    get:\n      description: Get one order\n",
+        )
+        .unwrap();
+
+        assert_eq!(resources.len(), 1);
+        let orders = &resources[0];
+        assert_eq!(orders.relative_uri, "/orders");
+        assert_eq!(orders.display_name.as_deref(), Some("Orders"));
+        assert!(orders.methods.contains_key("get"));
+        assert_eq!(orders.path_parameters(), Vec::<String>::new());
+
+        assert_eq!(orders.sub_resources.len(), 1);
+        let order = &orders.sub_resources[0];
+        assert_eq!(order.relative_uri, "/{orderId}");
+        assert_eq!(order.path_parameters(), vec!["orderId".to_string()]);
+    }
+
+    #[test]
+    fn rejects_empty_parameter_name() {
+        let error = parse("/{}:\n  get: {}\n").unwrap_err();
+        assert!(matches!(error, ParseError::InvalidUriTemplate { .. }));
+    }
+
+    #[test]
+    fn rejects_unbalanced_braces() {
+        let error = parse("/orders/{id:\n  get: {}\n").unwrap_err();
+        assert!(matches!(error, ParseError::InvalidUriTemplate { .. }));
+    }
+}
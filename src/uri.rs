@@ -1,5 +1,9 @@
+use serde::de::{self, Deserializer, Visitor};
+use serde::Deserialize;
 use std::{
+    collections::HashMap,
     convert::{TryFrom, TryInto},
+    fmt,
     str::FromStr,
 };
 use thiserror::Error;
@@ -9,6 +13,8 @@ use uriparse::uri::{URIError, URI as ParsedUri};
 pub enum ParseError {
     #[error("Invalid syntax: {0}")]
     InvalidSyntax(#[from] URIError),
+    #[error("Unbound URI template parameter: {0}")]
+    UnboundUriParameter(String),
 }
 
 #[derive(Debug)]
@@ -17,18 +23,121 @@ pub struct Uri {
 }
 
 impl Uri {
+    /// Parses the raw URI with `uriparse`. Panics if this `Uri` still has
+    /// unexpanded `{name}` templates (see `is_template`/`expand`) since those
+    /// are never valid URI syntax on their own.
     pub fn parsed(&self) -> ParsedUri {
         self.raw.as_str().try_into().expect("Failed to parse.")
     }
+
+    /// Whether this URI still has `{name}` templates pending expansion.
+    pub fn is_template(&self) -> bool {
+        self.raw.contains('{') || self.raw.contains('}')
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Substitutes each `{name}` template in this URI with its binding in
+    /// `params`, re-validating the result as a URI.
+    pub fn expand(&self, params: &HashMap<String, String>) -> Result<Uri, ParseError> {
+        let mut result = String::with_capacity(self.raw.len());
+        let mut rest = self.raw.as_str();
+
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .map(|offset| start + offset)
+                .ok_or_else(|| ParseError::UnboundUriParameter(rest[start..].to_string()))?;
+            let name = &rest[start + 1..end];
+            let value = params
+                .get(name)
+                .ok_or_else(|| ParseError::UnboundUriParameter(name.to_string()))?;
+            result.push_str(value);
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+
+        result.parse()
+    }
 }
 
 impl FromStr for Uri {
     type Err = ParseError;
 
+    /// RAML URIs (notably `baseUri`) commonly contain `{name}` templates,
+    /// e.g. `https://{host}/api/{version}`, which `uriparse` rejects outright.
+    /// Those are validated once `expand` has substituted every placeholder;
+    /// until then we only validate syntax for URIs that are already concrete.
     fn from_str(raw: &str) -> Result<Self, Self::Err> {
-        let _ = ParsedUri::try_from(raw)?;
-        Ok(Uri {
+        let uri = Uri {
             raw: raw.to_string(),
-        })
+        };
+        if !uri.is_template() {
+            let _ = ParsedUri::try_from(raw)?;
+        }
+        Ok(uri)
+    }
+}
+
+impl<'de> Deserialize<'de> for Uri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct UriVisitor;
+
+        impl<'de> Visitor<'de> for UriVisitor {
+            type Value = Uri;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a URI string")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Uri, E>
+            where
+                E: de::Error,
+            {
+                Uri::from_str(value).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_str(UriVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_templated_base_uri_without_validating_placeholders() {
+        let uri: Uri = "https://{host}/api/{version}".parse().unwrap();
+        assert!(uri.is_template());
+        assert_eq!(uri.as_str(), "https://{host}/api/{version}");
+    }
+
+    #[test]
+    fn expands_named_parameters_and_version() {
+        let uri: Uri = "https://{host}/api/{version}".parse().unwrap();
+        let mut params = HashMap::new();
+        params.insert("host".to_string(), "example.com".to_string());
+        params.insert("version".to_string(), "v1".to_string());
+
+        let expanded = uri.expand(&params).unwrap();
+
+        assert!(!expanded.is_template());
+        assert_eq!(expanded.as_str(), "https://example.com/api/v1");
+    }
+
+    #[test]
+    fn errors_on_unbound_parameter() {
+        let uri: Uri = "https://{host}/api".parse().unwrap();
+
+        let error = uri.expand(&HashMap::new()).unwrap_err();
+
+        assert!(matches!(error, ParseError::UnboundUriParameter(name) if name == "host"));
     }
 }